@@ -0,0 +1,26 @@
+//! Records the current git commit so `clippy-shim --version` can report it
+//! without a runtime dependency on `git`.
+
+use std::process::Command;
+
+fn main() {
+    let git_sha = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=CLIPPY_SHIM_GIT_SHA={git_sha}");
+
+    // `.git/HEAD` only changes on a branch switch. An ordinary commit to the
+    // current branch instead updates the branch's ref file and appends to
+    // the HEAD reflog, so watch the reflog (and `packed-refs`) too or the
+    // reported SHA goes stale after every commit.
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/logs/HEAD");
+    println!("cargo:rerun-if-changed=.git/packed-refs");
+}
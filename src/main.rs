@@ -37,6 +37,111 @@ struct DetectedFlags {
     has_target_selection: bool,
 }
 
+/// Find the value of a flag in `args`, modeled on Clippy's own driver helper.
+///
+/// For each argument, splits once on `=` into at most two pieces. If the head
+/// matches `find_arg`, the value is the tail after `=`, or, if there was no
+/// `=`, the *next* argument in the iterator. The value is only returned if it
+/// passes `pred`, which lets callers reject a value that merely looks like a
+/// flag's argument (e.g. a bare `--` swallowed as a value).
+#[must_use]
+fn arg_value<'a>(args: &'a [String], find_arg: &str, pred: impl Fn(&str) -> bool) -> Option<&'a str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut parts = arg.splitn(2, '=');
+        if parts.next() != Some(find_arg) {
+            continue;
+        }
+        let value = parts.next().or_else(|| args.next().map(String::as_str));
+        if let Some(value) = value {
+            if pred(value) {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
+/// Collect every `-p`/`--package` value, including the glued `-pfoo` form.
+#[must_use]
+fn collect_package_names(args: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(name) = arg.strip_prefix("--package=") {
+            names.push(name.to_string());
+        } else if arg == "-p" || arg == "--package" {
+            if let Some(name) = args.next() {
+                names.push(name.clone());
+            }
+        } else if let Some(name) = arg.strip_prefix("-p") {
+            if !name.is_empty() {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Collect every `--features`/`--features=` value, split on commas.
+#[must_use]
+fn collect_features(args: &[String]) -> Vec<String> {
+    let mut features = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = if let Some(value) = arg.strip_prefix("--features=") {
+            Some(value)
+        } else if arg == "--features" {
+            args.next().map(String::as_str)
+        } else {
+            None
+        };
+
+        if let Some(value) = value {
+            features.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|feature| !feature.is_empty())
+                    .map(ToString::to_string),
+            );
+        }
+    }
+    features
+}
+
+/// Environment variable that disables the enforced lint baseline entirely.
+///
+/// Set to any value other than `0` or an empty string to suppress
+/// `-Dclippy::all -Dclippy::pedantic`. Equivalent to passing `--no-baseline`.
+const NO_BASELINE_ENV_VAR: &str = "CLIPPY_SHIM_NO_BASELINE";
+
+/// Whether `var` is set in the environment to a "truthy" value (anything
+/// other than unset, empty, or `0`).
+#[must_use]
+fn env_flag_enabled(var: &str) -> bool {
+    std::env::var_os(var).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Remove every occurrence of a bare boolean `flag` from `args`, returning the
+/// filtered arguments and whether the flag was present.
+#[must_use]
+fn extract_bool_flag(args: Vec<String>, flag: &str) -> (Vec<String>, bool) {
+    let mut found = false;
+    let remaining = args
+        .into_iter()
+        .filter(|arg| {
+            if arg == flag {
+                found = true;
+                false
+            } else {
+                true
+            }
+        })
+        .collect();
+    (remaining, found)
+}
+
 /// Split CLI arguments into cargo arguments and clippy arguments.
 ///
 /// Cargo accepts extra arguments for the underlying tool (here: rustc/Clippy)
@@ -89,6 +194,25 @@ fn workspace_dir() -> PathBuf {
     dir
 }
 
+/// Read the `[package] name = "..."` declared in a `Cargo.toml` at `dir`,
+/// mirroring `CARGO_PRIMARY_PACKAGE` semantics: the crate that owns `dir` is
+/// the "primary" package, as opposed to its path dependencies.
+#[must_use]
+fn primary_package_name(dir: &std::path::Path) -> Option<String> {
+    let contents = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let section = find_table_section(&contents, "package")?;
+    for line in section.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() == "name" {
+            return Some(parse_toml_string(value).to_string());
+        }
+    }
+    None
+}
+
 /// Convert a process exit code to the range supported by [`ExitCode`].
 #[must_use]
 fn exit_code_from_i32(code: i32) -> u8 {
@@ -125,15 +249,20 @@ fn exit_code_from_status(status: std::process::ExitStatus) -> u8 {
 
 #[must_use]
 fn detect_flags(user_cargo_args: &[String]) -> DetectedFlags {
+    let has_package = !collect_package_names(user_cargo_args).is_empty();
+    let has_features = !collect_features(user_cargo_args).is_empty();
+    let has_manifest_path =
+        arg_value(user_cargo_args, "--manifest-path", |value| !value.is_empty()).is_some();
+
     let mut flags = DetectedFlags {
         scope: ScopeFlags {
-            has_package: false,
+            has_package,
             has_workspace: false,
-            has_manifest_path: false,
+            has_manifest_path,
         },
         feature_selection: FeatureSelectionFlags {
             has_all_features: false,
-            has_features: false,
+            has_features,
             has_no_default_features: false,
         },
         has_no_deps: false,
@@ -142,12 +271,6 @@ fn detect_flags(user_cargo_args: &[String]) -> DetectedFlags {
 
     for arg in user_cargo_args {
         match arg.as_str() {
-            "-p" | "--package" => {
-                flags.scope.has_package = true;
-            }
-            "--manifest-path" => {
-                flags.scope.has_manifest_path = true;
-            }
             "--workspace" => {
                 flags.scope.has_workspace = true;
             }
@@ -161,22 +284,10 @@ fn detect_flags(user_cargo_args: &[String]) -> DetectedFlags {
             "--all-features" => {
                 flags.feature_selection.has_all_features = true;
             }
-            "--features" => {
-                flags.feature_selection.has_features = true;
-            }
             "--no-default-features" => {
                 flags.feature_selection.has_no_default_features = true;
             }
             _ => {
-                if arg.starts_with("--package=") {
-                    flags.scope.has_package = true;
-                }
-                if arg.starts_with("-p") && arg.len() > 2 {
-                    flags.scope.has_package = true;
-                }
-                if arg.starts_with("--manifest-path=") {
-                    flags.scope.has_manifest_path = true;
-                }
                 if arg.starts_with("--bin=")
                     || arg.starts_with("--test=")
                     || arg.starts_with("--bench=")
@@ -184,9 +295,6 @@ fn detect_flags(user_cargo_args: &[String]) -> DetectedFlags {
                 {
                     flags.has_target_selection = true;
                 }
-                if arg.starts_with("--features=") {
-                    flags.feature_selection.has_features = true;
-                }
             }
         }
     }
@@ -209,16 +317,303 @@ fn strip_workspace_if_contradictory(
     user_cargo_args
 }
 
+/// Environment variable pointing at an explicit `clippy-shim.toml` path,
+/// overriding discovery from [`workspace_dir`].
+const SHIM_CONFIG_ENV_VAR: &str = "CLIPPY_SHIM_CONFIG";
+
+/// How `--features`/`--all-features` should be handled when the user did not
+/// select a feature mode explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeatureMode {
+    /// Inject `--all-features` (the historical default).
+    All,
+    /// Inject nothing; let cargo use the crate's default features.
+    Default,
+    /// Feature-powerset iteration is driven by an external tool (`cargo fc`);
+    /// a single invocation can't expand it, so this behaves like `Default`.
+    Powerset,
+}
+
+/// How `--all-targets`/target selection should be handled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetsMode {
+    /// Inject `--all-targets` (the historical default).
+    All,
+    /// Inject `--lib`.
+    Lib,
+}
+
+/// Repo-configurable defaults and lint baseline for `build_cargo_clippy_args`
+/// and `run_cargo_clippy`.
+///
+/// Discovered from a `clippy-shim.toml` at [`workspace_dir`] (or the path in
+/// `CLIPPY_SHIM_CONFIG`). [`ShimConfig::default`] injects this repo's
+/// original hardcoded defaults and lint baseline when no config file exists,
+/// but emits the baseline before user clippy args (see `strict_baseline`).
+// Each flag below is independently toggled by its own `clippy-shim.toml` key
+// rather than forming a mutually-exclusive state, so an enum wouldn't fit;
+// the struct is config data, not a type driving match-based control flow.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShimConfig {
+    no_deps: bool,
+    workspace: bool,
+    feature_mode: FeatureMode,
+    targets: TargetsMode,
+    /// When `true`, reproduce the original authoritative behavior: the
+    /// baseline is emitted *after* `user_clippy_args`, so the baseline always
+    /// wins. When `false` (the default), the baseline is emitted first so a
+    /// trailing user `-A`/`-W`/`-D` overrides it.
+    strict_baseline: bool,
+    /// When `true`, and the shim is not running at the workspace root, scope
+    /// the invocation to the package at the current directory (via `-p`
+    /// derived from its `Cargo.toml`) instead of letting cargo fall back to
+    /// its own directory-based default, so member path-dependencies are never
+    /// linted even when `--no-deps` is somehow bypassed.
+    only_primary: bool,
+    /// Lints appended as `-D<lint>`.
+    deny: Vec<String>,
+    /// Lints appended as `-W<lint>`.
+    warn: Vec<String>,
+    /// Lints appended as `-A<lint>`.
+    allow: Vec<String>,
+    /// Lints appended as `-F<lint>`.
+    forbid: Vec<String>,
+}
+
+impl Default for ShimConfig {
+    fn default() -> Self {
+        Self {
+            no_deps: true,
+            workspace: true,
+            feature_mode: FeatureMode::All,
+            targets: TargetsMode::All,
+            strict_baseline: false,
+            only_primary: false,
+            deny: vec!["clippy::all".to_string(), "clippy::pedantic".to_string()],
+            warn: Vec::new(),
+            allow: Vec::new(),
+            forbid: Vec::new(),
+        }
+    }
+}
+
+/// Parse the array on the right of `key = [...]`, stripping quotes and
+/// whitespace from each element.
+#[must_use]
+fn parse_toml_string_array(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(str::trim)
+        .map(|item| item.trim_matches('"').trim_matches('\''))
+        .filter(|item| !item.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Parse a bare TOML string value (`"all"`), stripping surrounding quotes.
+#[must_use]
+fn parse_toml_string(value: &str) -> &str {
+    value.trim().trim_matches('"').trim_matches('\'')
+}
+
+/// Parse a minimal, flat subset of TOML sufficient for `clippy-shim.toml`:
+/// `key = value` pairs, booleans, quoted strings, and quoted-string arrays
+/// (including arrays split across multiple lines, the idiomatic TOML style
+/// for anything longer than a couple of entries). Section headers
+/// (`[section]`) and comments (`#`) are ignored, so the keys below may live
+/// at the top level or under any table.
+#[must_use]
+fn parse_shim_config(contents: &str) -> ShimConfig {
+    let mut config = ShimConfig::default();
+
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() || line.starts_with('[') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let mut value = value.trim().to_string();
+
+        // An array that doesn't close on its own line continues on
+        // subsequent lines until one ends with `]`.
+        if value.starts_with('[') && !value.ends_with(']') {
+            for next_line in lines.by_ref() {
+                let next_line = next_line.split('#').next().unwrap_or("").trim();
+                value.push(' ');
+                value.push_str(next_line);
+                if next_line.ends_with(']') {
+                    break;
+                }
+            }
+        }
+        let value = value.as_str();
+
+        match key {
+            "no_deps" => config.no_deps = value == "true",
+            "workspace" => config.workspace = value == "true",
+            "strict_baseline" => config.strict_baseline = value == "true",
+            "only_primary" => config.only_primary = value == "true",
+            "feature_mode" => {
+                config.feature_mode = match parse_toml_string(value) {
+                    "default" => FeatureMode::Default,
+                    "powerset" => FeatureMode::Powerset,
+                    _ => FeatureMode::All,
+                };
+            }
+            "targets" => {
+                config.targets = match parse_toml_string(value) {
+                    "lib" => TargetsMode::Lib,
+                    _ => TargetsMode::All,
+                };
+            }
+            "deny" => config.deny = parse_toml_string_array(value),
+            "warn" => config.warn = parse_toml_string_array(value),
+            "allow" => config.allow = parse_toml_string_array(value),
+            "forbid" => config.forbid = parse_toml_string_array(value),
+            _ => {}
+        }
+    }
+
+    config
+}
+
+/// Extract the body of a TOML table, given its dotted header (e.g.
+/// `workspace.metadata.clippy-shim`), as the raw lines between its `[header]`
+/// line and the next top-level `[`. Returns `None` if the header isn't found.
+#[must_use]
+fn find_table_section<'a>(contents: &'a str, header: &str) -> Option<&'a str> {
+    let needle = format!("[{header}]");
+    let header_start = contents.find(&needle)?;
+    let body_start = contents[header_start..]
+        .find('\n')
+        .map_or(contents.len(), |offset| header_start + offset + 1);
+    let body = &contents[body_start..];
+    let body_end = body.find("\n[").map_or(body.len(), |offset| offset + 1);
+    Some(&body[..body_end])
+}
+
+/// Discover and load the shim config, in priority order:
+///
+/// 1. `CLIPPY_SHIM_CONFIG`, if set, pointing at a `clippy-shim.toml`-shaped file.
+/// 2. `clippy-shim.toml` at [`workspace_dir`].
+/// 3. The `[workspace.metadata.clippy-shim]` table in `Cargo.toml` at [`workspace_dir`].
+/// 4. [`ShimConfig::default`], this repo's original hardcoded defaults and lint baseline.
+#[must_use]
+fn load_shim_config() -> ShimConfig {
+    if let Some(path) = std::env::var_os(SHIM_CONFIG_ENV_VAR)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+    {
+        return std::fs::read_to_string(path)
+            .map(|contents| parse_shim_config(&contents))
+            .unwrap_or_default();
+    }
+
+    let workspace_dir = workspace_dir();
+
+    if let Ok(contents) = std::fs::read_to_string(workspace_dir.join("clippy-shim.toml")) {
+        return parse_shim_config(&contents);
+    }
+
+    if let Ok(contents) = std::fs::read_to_string(workspace_dir.join("Cargo.toml")) {
+        if let Some(section) = find_table_section(&contents, "workspace.metadata.clippy-shim") {
+            return parse_shim_config(section);
+        }
+    }
+
+    ShimConfig::default()
+}
+
+/// Collect every lint named in a user-supplied `-A`/`--allow` flag, including
+/// the glued `-Aclippy::foo` form.
+#[must_use]
+fn collect_allowed_lints(clippy_args: &[String]) -> Vec<String> {
+    let mut lints = Vec::new();
+    let mut args = clippy_args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(lint) = arg.strip_prefix("--allow=") {
+            lints.push(lint.to_string());
+        } else if arg == "-A" || arg == "--allow" {
+            if let Some(lint) = args.next() {
+                lints.push(lint.clone());
+            }
+        } else if let Some(lint) = arg.strip_prefix("-A") {
+            if !lint.is_empty() {
+                lints.push(lint.to_string());
+            }
+        }
+    }
+    lints
+}
+
+/// Build the `-D`/`-W`/`-A`/`-F` lint-level arguments for the enforced baseline
+/// from `config.deny`, `config.warn`, `config.allow`, and `config.forbid`, in
+/// that fixed order, skipping any lint named in `exempt`.
+///
+/// `exempt` is normally the set of lints the user already passed via a
+/// trailing `-A`/`--allow`, so that an explicit user override wins instead of
+/// being re-asserted (and potentially re-denied) by the baseline.
+#[must_use]
+fn baseline_lint_args(config: &ShimConfig, exempt: &[String]) -> Vec<String> {
+    let not_exempt = |lint: &&String| !exempt.iter().any(|allowed| *allowed == **lint);
+    let mut args = Vec::new();
+    args.extend(
+        config
+            .deny
+            .iter()
+            .filter(not_exempt)
+            .map(|lint| format!("-D{lint}")),
+    );
+    args.extend(
+        config
+            .warn
+            .iter()
+            .filter(not_exempt)
+            .map(|lint| format!("-W{lint}")),
+    );
+    args.extend(
+        config
+            .allow
+            .iter()
+            .filter(not_exempt)
+            .map(|lint| format!("-A{lint}")),
+    );
+    args.extend(
+        config
+            .forbid
+            .iter()
+            .filter(not_exempt)
+            .map(|lint| format!("-F{lint}")),
+    );
+    args
+}
+
 #[must_use]
 fn build_cargo_clippy_args(
     cargo_args: Vec<String>,
     user_cargo_args: Vec<String>,
     flags: DetectedFlags,
     is_workspace_root: bool,
+    config: &ShimConfig,
+    primary_package: Option<&str>,
 ) -> Vec<String> {
     let mut cargo_clippy_args = Vec::new();
     cargo_clippy_args.extend(cargo_args);
 
+    let scope_to_primary = config.only_primary
+        && !is_workspace_root
+        && !flags.scope.has_package
+        && !flags.scope.has_manifest_path
+        && primary_package.is_some();
+
     // Only default to workspace linting when:
     // - we are invoked from the workspace root, and
     // - the user did not pass any narrower scope.
@@ -226,27 +621,36 @@ fn build_cargo_clippy_args(
     // This is critical for `cargo fc`, which runs this wrapper from each package's
     // directory without forwarding `-p`.
     if is_workspace_root
+        && config.workspace
         && !flags.scope.has_package
         && !flags.scope.has_manifest_path
         && !flags.scope.has_workspace
     {
         cargo_clippy_args.push("--workspace".to_string());
+    } else if let Some(name) = primary_package.filter(|_| scope_to_primary) {
+        cargo_clippy_args.push("-p".to_string());
+        cargo_clippy_args.push(name.to_string());
     }
 
     // By default lint all targets. However, respect explicit target selection.
     if !flags.has_target_selection {
-        cargo_clippy_args.push("--all-targets".to_string());
+        match config.targets {
+            TargetsMode::All => cargo_clippy_args.push("--all-targets".to_string()),
+            TargetsMode::Lib => cargo_clippy_args.push("--lib".to_string()),
+        }
     }
 
     // Always prefer `--no-deps` so we don't fail on lints from dependency crates
-    // when we enforce `-Dclippy::...`.
-    if !flags.has_no_deps {
+    // when we enforce `-Dclippy::...`. A config's `no_deps = false` turns this
+    // off, except while scoping to the primary package.
+    if (config.no_deps || scope_to_primary) && !flags.has_no_deps {
         cargo_clippy_args.push("--no-deps".to_string());
     }
 
     // Default to linting with all features enabled unless the user explicitly
     // selected some other feature mode.
-    if !flags.feature_selection.has_all_features
+    if config.feature_mode == FeatureMode::All
+        && !flags.feature_selection.has_all_features
         && !flags.feature_selection.has_features
         && !flags.feature_selection.has_no_default_features
     {
@@ -278,76 +682,287 @@ fn build_cargo_clippy_args(
 ///
 /// ## Defaults and rationale
 ///
-/// - **`--no-deps`**: always enabled (unless the user passed it) so that dependency
-///   crates do not produce clippy diagnostics. We still compile dependencies, but we
-///   avoid turning dependency lints into hard errors when we enforce `-D clippy::...`.
+/// - **`--no-deps`**: enabled by default (unless the user passed it, or
+///   `no_deps = false` in the config) so that dependency crates do not produce
+///   clippy diagnostics. We still compile dependencies, but we avoid turning
+///   dependency lints into hard errors when we enforce `-D clippy::...`.
 ///
 /// - **`--all-targets`**: enabled by default so we lint library, binaries, tests,
 ///   benches, and examples. If the user *already selected specific targets*
-///   (`--lib`, `--bin`, `--tests`, etc.), we do not add `--all-targets`.
+///   (`--lib`, `--bin`, `--tests`, etc.), we do not add `--all-targets`. A config's
+///   `targets = "lib"` injects `--lib` instead.
 ///
 /// - **`--all-features`**: enabled by default unless the user already provided an
 ///   explicit feature selection (`--all-features`, `--features`, or
-///   `--no-default-features`). This keeps `cargo lint` useful without requiring
-///   explicit feature flags.
+///   `--no-default-features`), or the config sets `feature_mode` to `"default"` or
+///   `"powerset"`. This keeps `cargo lint` useful without requiring explicit
+///   feature flags.
+///
+/// - **`--workspace`**: only enabled by default when running from the workspace root,
+///   the config's `workspace` is `true` (the default), and the user did not specify a
+///   narrower scope (`-p`, `--manifest-path`, or `--workspace`). When running inside a
+///   package directory (as `cargo fc` does), we *do not* force `--workspace`.
 ///
-/// - **`--workspace`**: only enabled by default when running from the workspace root
-///   and the user did not specify a narrower scope (`-p`, `--manifest-path`, or
-///   `--workspace`). When running inside a package directory (as `cargo fc` does),
-///   we *do not* force `--workspace`.
+/// - **Lint baseline**: built from `config.deny`/`config.warn`/`config.allow`/
+///   `config.forbid` (`clippy::all` and `clippy::pedantic` denied when no config
+///   exists). Clippy applies lint-level flags left-to-right, so the baseline is
+///   emitted *before* `user_clippy_args`, letting a trailing user `-A`/`-W`/`-D`
+///   win (e.g. `cargo lint -- -A clippy::useless_format`). With
+///   `strict_baseline = true` in the config it is emitted *after* them instead,
+///   so the baseline wins. Pass `--no-baseline` or set
+///   `CLIPPY_SHIM_NO_BASELINE=1` to suppress the baseline entirely.
 ///
-/// - **`-Dclippy::all` / `-Dclippy::pedantic`**: always appended to enforce a strict
-///   lint baseline for this repository. These are intentionally appended after any
-///   user-provided clippy args so the wrapper remains authoritative.
+/// The config itself is read once from `clippy-shim.toml` discovered at
+/// [`workspace_dir`], or from the path in `CLIPPY_SHIM_CONFIG`; see
+/// [`load_shim_config`].
 ///
 /// # Errors
 ///
+/// Quote `arg` for copy-pasting into a POSIX shell, only when it actually
+/// needs it.
+#[must_use]
+fn shell_quote(arg: &str) -> String {
+    let needs_quoting = arg.is_empty()
+        || arg
+            .chars()
+            .any(|c| c.is_whitespace() || "\"'$`\\*?[]{}()<>|&;".contains(c));
+
+    if needs_quoting {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    } else {
+        arg.to_string()
+    }
+}
+
+/// Canonicalize the final after-`--` clippy argument tail so that logically
+/// identical invocations produce the same argv, keeping clippy-driver's
+/// dep-info fingerprint (which it derives from its own argv, not anything we
+/// set) stable across repeated `cargo fc` runs and `lint`/`fixit` alternation.
+///
+/// Glues any split `-D`/`-W`/`-A`/`-F <lint>` and `--deny`/`--warn`/
+/// `--allow`/`--forbid <lint>` pair into its single-token form, then drops
+/// duplicates while keeping the first occurrence. Dropping a later copy
+/// changes a lint's effective level only when a conflicting flag for that
+/// lint sits between the two copies (`-Dx -Ax -Dx` becomes `-Dx -Ax`).
+#[must_use]
+fn canonicalize_clippy_tail(tail: Vec<String>) -> Vec<String> {
+    let mut glued = Vec::with_capacity(tail.len());
+    let mut args = tail.into_iter();
+    while let Some(arg) = args.next() {
+        if matches!(arg.as_str(), "-D" | "-W" | "-A" | "-F") {
+            if let Some(value) = args.next() {
+                glued.push(format!("{arg}{value}"));
+                continue;
+            }
+        }
+        if matches!(arg.as_str(), "--deny" | "--warn" | "--allow" | "--forbid") {
+            if let Some(value) = args.next() {
+                glued.push(format!("{arg}={value}"));
+                continue;
+            }
+        }
+        glued.push(arg);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    glued.into_iter().filter(|arg| seen.insert(arg.clone())).collect()
+}
+
+/// Render the fully assembled `cargo clippy` invocation in a copy-pasteable,
+/// shell-quoted form.
+#[must_use]
+fn render_command_line(cargo_clippy_args: &[String], clippy_tail: &[String]) -> String {
+    let mut parts = vec!["cargo".to_string(), "clippy".to_string()];
+    parts.extend(cargo_clippy_args.iter().cloned());
+    parts.push("--".to_string());
+    parts.extend(clippy_tail.iter().cloned());
+
+    parts
+        .iter()
+        .map(|part| shell_quote(part))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Returns an error if spawning or waiting on the `cargo clippy` process fails.
 fn run_cargo_clippy(
     cargo_args: Vec<String>,
     args: Vec<String>,
-) -> Result<std::process::ExitStatus, std::io::Error> {
+) -> Result<Option<std::process::ExitStatus>, std::io::Error> {
     let (user_cargo_args, user_clippy_args) = split_args_on_double_dash(args);
+    let (user_cargo_args, has_no_baseline_flag) =
+        extract_bool_flag(user_cargo_args, "--no-baseline");
+    let no_baseline = has_no_baseline_flag || env_flag_enabled(NO_BASELINE_ENV_VAR);
+    let (user_cargo_args, has_dry_run_flag) = extract_bool_flag(user_cargo_args, "--dry-run");
+    let (user_cargo_args, has_print_command_flag) =
+        extract_bool_flag(user_cargo_args, "--print-command");
+    let dry_run = has_dry_run_flag || has_print_command_flag;
+    let (user_cargo_args, has_only_primary_flag) =
+        extract_bool_flag(user_cargo_args, "--only-primary");
+
+    let mut config = load_shim_config();
+    config.only_primary |= has_only_primary_flag;
 
     let workspace_dir = workspace_dir();
-    let is_workspace_root = std::env::current_dir()
-        .ok()
-        .is_some_and(|current_dir| current_dir == workspace_dir);
+    let current_dir = std::env::current_dir().ok();
+    let is_workspace_root = current_dir
+        .as_ref()
+        .is_some_and(|current_dir| *current_dir == workspace_dir);
+    let primary_package = current_dir
+        .as_deref()
+        .and_then(primary_package_name);
 
     let flags = detect_flags(&user_cargo_args);
 
     // If the user explicitly scoped to a single package or manifest path, we treat
     // `--workspace` as a contradiction and drop it.
-    let user_cargo_args = strip_workspace_if_contradictory(user_cargo_args, flags);
+    let user_cargo_args = strip_workspace_if_contradictory(user_cargo_args, flags.clone());
+
+    let cargo_clippy_args = build_cargo_clippy_args(
+        cargo_args,
+        user_cargo_args,
+        flags,
+        is_workspace_root,
+        &config,
+        primary_package.as_deref(),
+    );
+
+    let mut clippy_tail = Vec::new();
+    if config.strict_baseline {
+        clippy_tail.extend(user_clippy_args);
+        if !no_baseline {
+            clippy_tail.extend(baseline_lint_args(&config, &[]));
+        }
+    } else {
+        if !no_baseline {
+            let exempted = collect_allowed_lints(&user_clippy_args);
+            clippy_tail.extend(baseline_lint_args(&config, &exempted));
+        }
+        clippy_tail.extend(user_clippy_args);
+    }
+
+    let clippy_tail = canonicalize_clippy_tail(clippy_tail);
 
-    let cargo_clippy_args =
-        build_cargo_clippy_args(cargo_args, user_cargo_args, flags, is_workspace_root);
+    if dry_run {
+        println!("{}", render_command_line(&cargo_clippy_args, &clippy_tail));
+        return Ok(None);
+    }
 
     let mut command = std::process::Command::new("cargo");
     command.arg("clippy");
-    command.args(cargo_clippy_args);
+    command.args(&cargo_clippy_args);
     command.arg("--");
-    command.args(user_clippy_args);
-    command.arg("-Dclippy::all");
-    command.arg("-Dclippy::pedantic");
+    command.args(&clippy_tail);
+
+    command.status().map(Some)
+}
+
+/// The shim's own package version, set by cargo at build time.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The git commit the shim was built from, set by `build.rs`.
+const GIT_SHA: &str = env!("CLIPPY_SHIM_GIT_SHA");
 
-    command.status()
+/// Build a `<program> <version> (<git sha>)` line, matching the convention
+/// used by Clippy's own `--version` output.
+#[must_use]
+fn shim_version_string(program_name: &str) -> String {
+    format!("{program_name} {VERSION} ({GIT_SHA})")
+}
+
+/// Resolve `cargo clippy --version`, trimmed, or `None` if cargo or clippy
+/// could not be invoked.
+#[must_use]
+fn cargo_clippy_version() -> Option<String> {
+    let output = std::process::Command::new("cargo")
+        .args(["clippy", "--version"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|version| version.trim().to_string())
+}
+
+/// Print the shim's own version alongside the resolved `cargo clippy`
+/// version, following Clippy's own binary.
+fn print_version(program_name: &str) {
+    println!("{}", shim_version_string(program_name));
+    match cargo_clippy_version() {
+        Some(version) => println!("{version}"),
+        None => eprintln!("warning: failed to resolve `cargo clippy --version`"),
+    }
 }
 
-fn usage(program_name: &str) {
+/// Print the underlying `cargo clippy --help` output so users can discover
+/// the real flags this wrapper forwards, beyond the ones it injects itself.
+fn print_cargo_clippy_help() {
+    let output = std::process::Command::new("cargo")
+        .args(["clippy", "--help"])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+        }
+        _ => eprintln!("warning: failed to resolve `cargo clippy --help`"),
+    }
+}
+
+fn usage(program_name: &str, config: &ShimConfig) {
+    let baseline = baseline_lint_args(config, &[]).join(" ");
     eprintln!("Usage:");
     eprintln!("  {program_name} lint [cargo clippy args] [-- clippy args]");
     eprintln!("  {program_name} fixit [cargo clippy args] [-- clippy args]");
+    eprintln!("  {program_name} --version | -V");
+    eprintln!();
+    eprintln!("Injected defaults (unless already selected, or suppressed by config):");
+    eprintln!("  --workspace     at the workspace root, unless -p/--manifest-path/--workspace is given");
+    eprintln!("  --all-targets   unless a target is already selected (--lib, --bin, ...)");
+    eprintln!("  --no-deps       unless already passed");
+    eprintln!("  --all-features  unless a feature selection is already made");
+    if config.strict_baseline {
+        eprintln!("  {baseline}   lint baseline, emitted after any `-- ` clippy args (strict_baseline = true)");
+    } else {
+        eprintln!("  {baseline}   lint baseline, emitted before any `-- ` clippy args");
+    }
+    eprintln!();
+    eprintln!("Overrides:");
+    eprintln!("  --no-baseline               suppress the injected lint baseline for this invocation");
+    eprintln!("  CLIPPY_SHIM_NO_BASELINE=1   same, via environment variable");
+    eprintln!("  clippy-shim.toml            override injected defaults and the lint baseline");
+    eprintln!("  CLIPPY_SHIM_CONFIG=<path>   use an explicit config file instead of workspace discovery");
+    eprintln!("  --dry-run, --print-command  print the assembled `cargo clippy` invocation and exit 0");
+    eprintln!(
+        "  a trailing -A/--allow      wins over a denied baseline lint, unless strict_baseline = true"
+    );
+    eprintln!(
+        "  --only-primary, only_primary = true   scope to the package at the current directory (-p <name> + --no-deps)"
+    );
 }
 
 fn main() -> ExitCode {
-    let mut args_iter = std::env::args();
-    let program_name = args_iter
-        .next()
-        .unwrap_or_else(|| "clippy-wrapper".to_string());
+    let mut args: Vec<String> = std::env::args().collect();
+    let program_name = if args.is_empty() {
+        "clippy-wrapper".to_string()
+    } else {
+        args.remove(0)
+    };
+
+    // Scan the whole argv, not just the first argument, so `--version`/`-V`
+    // is recognized even after a subcommand (`clippy-shim lint --version`).
+    if args.iter().any(|arg| arg == "--version" || arg == "-V") {
+        print_version(&program_name);
+        return ExitCode::SUCCESS;
+    }
 
+    let mut args_iter = args.into_iter();
     let Some(subcommand) = args_iter.next() else {
-        usage(&program_name);
+        usage(&program_name, &load_shim_config());
         return ExitCode::from(2);
     };
 
@@ -364,18 +979,22 @@ fn main() -> ExitCode {
             remaining_args,
         ),
         "-h" | "--help" | "help" => {
-            usage(&program_name);
+            usage(&program_name, &load_shim_config());
+            eprintln!();
+            println!("Underlying `cargo clippy --help`:");
+            print_cargo_clippy_help();
             return ExitCode::from(0);
         }
         _ => {
             eprintln!("unknown subcommand: {subcommand}");
-            usage(&program_name);
+            usage(&program_name, &load_shim_config());
             return ExitCode::from(2);
         }
     };
 
     let status = match run_cargo_clippy(cargo_args, args) {
-        Ok(status) => status,
+        Ok(Some(status)) => status,
+        Ok(None) => return ExitCode::SUCCESS,
         Err(err) => {
             eprintln!("failed to run cargo clippy: {err}");
             return ExitCode::from(1);